@@ -34,6 +34,12 @@ struct Config {
     /// Don't descend into directories with more than `n` entries
     #[structopt(long = "filelimit")]
     file_limit: Option<usize>,
+    /// Number of threads to use when traversing directories (0 = all cores)
+    #[structopt(long, default_value = "0")]
+    threads: usize,
+    /// Annotate entries with their git working-tree status
+    #[structopt(long)]
+    git: bool,
 
     // globs
     /// Glob / literal filenames to match (accepts multiple e.g. -P <first> -P <second>)
@@ -67,7 +73,7 @@ struct Config {
     no_color: bool,
     /// Print file size in bytes
     #[structopt(short)]
-    size: bool, // TODO: add sizes
+    size: bool,
     /// Print human-readable file size
     #[structopt(short)]
     human_size: bool,
@@ -82,13 +88,13 @@ struct Config {
     no_report: bool, // TODO: consider adding a report for this to silence
     /// Character set to use in output
     #[structopt(long, default_value = "UTF-8")]
-    charset: String, // TODO: determine usefulness of switching charsets
+    charset: String,
     /// Print the last modification time of each file
     #[structopt(short = "D")]
     mod_time: bool,
     /// Date format string
     #[structopt(long = "timefmt")]
-    time_format: Option<String>, // TODO: implement dates
+    time_format: Option<String>,
 
     // output
     /// Send output to a file
@@ -127,6 +133,14 @@ fn main() -> IOResult<()> {
     let current_dir = PathBuf::from(".");
     let dir = cfg.dir.as_ref().unwrap_or(&current_dir);
 
+    if let Err(e) = rayon::ThreadPoolBuilder::new()
+        .num_threads(cfg.threads)
+        .build_global()
+    {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(1);
+    }
+
     let positive = match pattern_ify(cfg.keep_pattern) {
         Ok(v) => v,
         Err(e) => {
@@ -142,6 +156,35 @@ fn main() -> IOResult<()> {
         }
     };
 
+    let charset = match Charset::from_name(&cfg.charset) {
+        Some(c) => c,
+        None => {
+            eprintln!("ERROR: unknown charset '{}'", cfg.charset);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(fmt) = &cfg.time_format {
+        if !is_valid_strftime(fmt) {
+            eprintln!("ERROR: invalid time format '{}'", fmt);
+            std::process::exit(1);
+        }
+    }
+
+    let show_size = cfg.size || cfg.human_size || cfg.si_size || cfg.du_size;
+
+    let git_status = if cfg.git {
+        find_git_root(dir).map(|root| git_status_map(&root))
+    } else {
+        None
+    };
+
+    let root_dev = if cfg.stay_on_fs {
+        file_device(dir)
+    } else {
+        None
+    };
+
     let search_opts = SearchOpts {
         show_hidden: cfg.all,
         dirs_only: cfg.directories,
@@ -152,6 +195,11 @@ fn main() -> IOResult<()> {
         positive_patterns: &positive,
         negative_patterns: &negative,
         case_insensitive_match: cfg.case_insensitive,
+        compute_size: show_size,
+        aggregate_dir_size: cfg.du_size,
+        git_status: git_status.as_ref(),
+        file_limit: cfg.file_limit,
+        root_dev,
         ..Default::default()
     };
 
@@ -169,6 +217,12 @@ fn main() -> IOResult<()> {
         indent: !cfg.no_indent,
         quote_names: cfg.quote_names,
         html_links: !cfg.no_links,
+        show_size,
+        human_size: cfg.human_size,
+        si_size: cfg.si_size,
+        show_mod_time: cfg.mod_time,
+        time_format: cfg.time_format,
+        charset,
     });
 
     if cfg.prune_dirs {
@@ -177,6 +231,12 @@ fn main() -> IOResult<()> {
 
     tree.sort_children();
 
+    let summary = if cfg.no_report {
+        None
+    } else {
+        Some(tree.summarize())
+    };
+
     let output: Box<Write> = if let Some(file) = cfg.output {
         Box::new(File::create(file)?)
     } else {
@@ -186,11 +246,20 @@ fn main() -> IOResult<()> {
     let mut buffered = BufWriter::new(output);
 
     if cfg.html_out {
-        buffered.write_all(tree.to_html().as_bytes())?;
+        buffered.write_all(tree.to_html(summary).as_bytes())?;
     } else if cfg.json_out {
-        serde_json::to_writer(&mut buffered, &tree)?;
+        if let Some(report) = summary {
+            serde_json::to_writer(&mut buffered, &Report { report, tree: &tree })?;
+        } else {
+            serde_json::to_writer(&mut buffered, &tree)?;
+        }
     } else {
         buffered.write_all(tree.to_string().as_bytes())?;
+
+        if let Some(summary) = summary {
+            writeln!(buffered)?;
+            write!(buffered, "{}", summary.render(cfg.human_size, cfg.si_size))?;
+        }
     }
 
     buffered.write(b"\n")?;
@@ -3,6 +3,7 @@
 #[macro_use]
 extern crate serde_derive;
 
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt;
 use std::fs::{self, File};
@@ -10,14 +11,54 @@ use std::io::{self, BufRead, BufReader};
 use std::mem::replace;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
 
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, Utc};
 use colored::{ColoredString, Colorize};
 use glob::{GlobError, MatchOptions, Pattern, PatternError};
+use rayon::prelude::*;
+
+/// The box-drawing glyphs used to render indentation, selectable with `--charset`.
+#[derive(Clone, Copy)]
+pub struct Charset {
+    pub super_dir: &'static str,
+    pub parent_nth: &'static str,
+    pub parent_last: &'static str,
+    pub indent: &'static str,
+}
+
+impl Charset {
+    pub const UTF8: Self = Self {
+        super_dir: "\u{2502}",
+        parent_nth: "\u{251c}",
+        parent_last: "\u{2514}",
+        indent: "\u{2500}\u{2500} ",
+    };
+
+    pub const ASCII: Self = Self {
+        super_dir: "|",
+        parent_nth: "|",
+        parent_last: "`",
+        indent: "-- ",
+    };
+
+    /// Look up a preset by name (case-insensitive); `None` for an unknown charset.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::UTF8),
+            "ascii" => Some(Self::ASCII),
+            _ => None,
+        }
+    }
+}
 
-const SUPER_DIR: char = '\u{2502}';
-const PARENT_NTH: char = '\u{251c}';
-const PARENT_LAST: char = '\u{2514}';
-const INDENT: &str = "\u{2500}\u{2500} ";
+impl Default for Charset {
+    fn default() -> Self {
+        Self::UTF8
+    }
+}
 
 #[derive(Clone, Copy, Default)]
 pub struct SearchOpts<'a> {
@@ -32,9 +73,153 @@ pub struct SearchOpts<'a> {
     pub positive_patterns: &'a [Pattern],
     pub negative_patterns: &'a [Pattern],
     pub case_insensitive_match: bool,
+    /// Populate `Dir::size` for files/executables/symlinks.
+    pub compute_size: bool,
+    /// Additionally populate `Dir::size` for directories, summing descendant
+    /// leaf sizes (classic `tree`'s `--du`, as opposed to `-s`'s per-entry size).
+    pub aggregate_dir_size: bool,
+    /// Map of absolute paths to their git working-tree status, when `--git` is in effect.
+    pub git_status: Option<&'a HashMap<PathBuf, GitStatus>>,
+    /// Don't recur into directories with more entries than this.
+    pub file_limit: Option<usize>,
+    /// The device the search started on, used to detect filesystem boundaries
+    /// when `stay_on_fs` is set.
+    pub root_dev: Option<u64>,
 }
 
-#[derive(Clone, Copy, Default)]
+/// A file or directory's two-letter `git status --porcelain` code, collapsed
+/// into the single condition a renderer cares about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Untracked,
+    Ignored,
+}
+
+impl GitStatus {
+    fn from_xy(x: u8, y: u8) -> Option<Self> {
+        match (x, y) {
+            (b'?', b'?') => Some(GitStatus::Untracked),
+            (b'!', b'!') => Some(GitStatus::Ignored),
+            (b' ', b' ') => None,
+            _ if x == b'D' || y == b'D' => Some(GitStatus::Deleted),
+            _ if x == b'R' || y == b'R' => Some(GitStatus::Renamed),
+            _ if x == b'A' => Some(GitStatus::Added),
+            _ => Some(GitStatus::Modified),
+        }
+    }
+
+    /// Severity ordering used to pick the "worst" status in a directory's subtree.
+    fn severity(self) -> u8 {
+        match self {
+            GitStatus::Deleted => 5,
+            GitStatus::Renamed => 4,
+            GitStatus::Modified => 3,
+            GitStatus::Added => 2,
+            GitStatus::Untracked => 1,
+            GitStatus::Ignored => 0,
+        }
+    }
+
+    fn worst(self, other: Self) -> Self {
+        if other.severity() > self.severity() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            GitStatus::Added => "A",
+            GitStatus::Modified => "M",
+            GitStatus::Deleted => "D",
+            GitStatus::Renamed => "R",
+            GitStatus::Untracked => "?",
+            GitStatus::Ignored => "!",
+        }
+    }
+}
+
+/// The device a path resides on, used to detect filesystem boundaries for `--stay-on-fs`.
+#[cfg(unix)]
+pub fn file_device(path: &PathBuf) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    path.metadata().ok().map(|m| m.dev())
+}
+
+/// No concept of a device number off of Unix, so filesystem boundaries are never detected.
+#[cfg(not(unix))]
+pub fn file_device(_path: &PathBuf) -> Option<u64> {
+    None
+}
+
+/// Check that `fmt` has no invalid `strftime` specifiers, so a bad `--timefmt`
+/// can be rejected at startup instead of panicking the first time it's used
+/// to format a modification time.
+pub fn is_valid_strftime(fmt: &str) -> bool {
+    !StrftimeItems::new(fmt).any(|item| item == Item::Error)
+}
+
+/// Walk upward from `start` looking for a `.git` directory, the way `git` itself does.
+pub fn find_git_root(start: &Path) -> Option<PathBuf> {
+    let mut dir = start.canonicalize().ok()?;
+
+    loop {
+        if dir.join(".git").exists() {
+            return Some(dir);
+        }
+
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// Run `git status --porcelain=v1 -z` once from `repo_root` and parse it into
+/// a map of absolute path to status, the way eza's git column does.
+pub fn git_status_map(repo_root: &Path) -> HashMap<PathBuf, GitStatus> {
+    let mut map = HashMap::new();
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_root)
+        .args(&["status", "--porcelain=v1", "-z"])
+        .output();
+
+    let output = match output {
+        Ok(o) if o.status.success() => o,
+        _ => return map,
+    };
+
+    let mut fields = output.stdout.split(|&b| b == 0).filter(|f| !f.is_empty());
+
+    while let Some(entry) = fields.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+
+        let (x, y) = (entry[0], entry[1]);
+        let path = repo_root.join(String::from_utf8_lossy(&entry[3..]).into_owned());
+
+        if let Some(status) = GitStatus::from_xy(x, y) {
+            map.insert(path, status);
+        }
+
+        // renamed/copied entries are followed by a second, original-path field
+        if x == b'R' || x == b'C' {
+            fields.next();
+        }
+    }
+
+    map
+}
+
+#[derive(Clone, Default)]
 pub struct FormatOpts {
     pub colorize: bool,
     pub decorate: bool,
@@ -42,6 +227,42 @@ pub struct FormatOpts {
     pub indent: bool,
     pub quote_names: bool,
     pub html_links: bool,
+    /// Print the aggregated size alongside each entry.
+    pub show_size: bool,
+    /// Format sizes as human-readable units (binary, 1024-based).
+    pub human_size: bool,
+    /// Format sizes as human-readable units (SI, 1000-based).
+    pub si_size: bool,
+    /// Print each entry's last modification time.
+    pub show_mod_time: bool,
+    /// `strftime`-style format string for `show_mod_time`; `None` uses the `ls`-like default.
+    pub time_format: Option<String>,
+    /// Box-drawing glyphs for indentation, selected with `--charset`.
+    pub charset: Charset,
+}
+
+fn human_readable_size(bytes: u64, si: bool) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let base = if si { 1000_f64 } else { 1024_f64 };
+
+    // Precision loss above 2^53 bytes is fine: `value` is only ever used to
+    // pick a unit and round to one decimal place for display.
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= base && unit < UNITS.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[unit])
+    } else if value < 10.0 {
+        format!("{:.1}{}", value, UNITS[unit])
+    } else {
+        format!("{:.0}{}", value, UNITS[unit])
+    }
 }
 
 #[derive(Clone, Serialize)]
@@ -83,6 +304,89 @@ impl FType {
     }
 }
 
+/// Traversal totals printed after a listing, suppressed by `--noreport`.
+#[derive(Clone, Copy, Default, Serialize)]
+pub struct Summary {
+    pub directories: usize,
+    pub files: usize,
+    pub executables: usize,
+    pub symlinks: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<u64>,
+}
+
+impl Summary {
+    fn leaf(ftype: &FType, size: Option<u64>) -> Self {
+        let mut summary = Self {
+            total_size: size,
+            ..Self::default()
+        };
+
+        match ftype {
+            FType::Dir => summary.directories = 1,
+            FType::Exe => summary.executables = 1,
+            FType::Link(_) => summary.symlinks = 1,
+            FType::File => summary.files = 1,
+        }
+
+        summary
+    }
+
+    fn fold(self, other: Self) -> Self {
+        Self {
+            directories: self.directories + other.directories,
+            files: self.files + other.files,
+            executables: self.executables + other.executables,
+            symlinks: self.symlinks + other.symlinks,
+            total_size: match (self.total_size, other.total_size) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+fn pluralize(n: usize, singular: &str, plural: &str) -> String {
+    if n == 1 {
+        format!("1 {}", singular)
+    } else {
+        format!("{} {}", n, plural)
+    }
+}
+
+/// A tree's JSON representation alongside its traversal `Summary`, produced
+/// when the report is not suppressed with `--noreport`.
+#[derive(Serialize)]
+pub struct Report<'a> {
+    pub report: Summary,
+    #[serde(flatten)]
+    pub tree: &'a Dir,
+}
+
+impl Summary {
+    /// Render the "N directories, M files" report line, appending the
+    /// aggregated size when a size flag populated `total_size`.
+    pub fn render(&self, human_size: bool, si_size: bool) -> String {
+        let files = self.files + self.executables + self.symlinks;
+
+        let mut out = format!(
+            "{}, {}",
+            pluralize(self.directories, "directory", "directories"),
+            pluralize(files, "file", "files")
+        );
+
+        if let Some(total) = self.total_size {
+            if human_size || si_size {
+                out.push_str(&format!(", {} total", human_readable_size(total, si_size)));
+            } else {
+                out.push_str(&format!(", {} bytes total", total));
+            }
+        }
+
+        out
+    }
+}
+
 #[derive(Clone, Serialize)]
 pub struct Dir {
     #[serde(rename = "name")]
@@ -93,18 +397,46 @@ pub struct Dir {
     ftype: FType,
     #[serde(skip)]
     read_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(rename = "git", skip_serializing_if = "Option::is_none")]
+    git_status: Option<GitStatus>,
+    /// Set when this directory has more entries than `--filelimit` and was
+    /// left unopened as a result.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    #[serde(
+        rename = "mod_time",
+        serialize_with = "serialize_mod_time",
+        skip_serializing_if = "Option::is_none"
+    )]
+    mod_time: Option<SystemTime>,
     #[serde(skip)]
     nest: Vec<bool>,
     #[serde(skip)]
     format: FormatOpts,
 }
 
+fn serialize_mod_time<S>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match time {
+        Some(t) => serializer.serialize_some(&DateTime::<Utc>::from(*t).to_rfc3339()),
+        None => serializer.serialize_none(),
+    }
+}
+
 impl Default for Dir {
     fn default() -> Self {
         Self {
             path: PathBuf::new(),
             ftype: FType::File,
             read_only: false,
+            size: None,
+            git_status: None,
+            truncated: false,
+            mod_time: None,
             contents: Vec::new(),
             nest: Vec::new(),
             format: FormatOpts::default(),
@@ -159,37 +491,62 @@ impl Dir {
             None => (true, None),
         };
 
+        let crosses_fs = cfg.stay_on_fs && cfg.root_dev.is_some() && file_device(obj) != cfg.root_dev;
+        let should_recur = should_recur && !crosses_fs;
+
         if obj.is_dir() && should_recur {
             if should_follow_link || link_contents.is_err() {
-                let ignore_list = if cfg.use_gitignores {
-                    VcsIgnore::in_dir_or_default(obj)
+                let truncated = cfg
+                    .file_limit
+                    .map_or(false, |limit| fs::read_dir(obj).unwrap().count() > limit);
+
+                let contents = if truncated {
+                    Vec::new()
                 } else {
-                    VcsIgnore::default()
-                }
-                .compose(cfg.vcs_blacklist_patterns, cfg.vcs_whitelist_patterns);
-
-                let contents = fs::read_dir(obj)
-                    .unwrap()
-                    .map(Result::unwrap)
-                    .filter(|e| !cfg.dirs_only || e.metadata().unwrap().is_dir())
-                    .filter_map(|e| {
-                        Self::from(
-                            &e.path(),
-                            SearchOpts {
-                                max_depth,
-                                vcs_blacklist_patterns: &ignore_list.black,
-                                vcs_whitelist_patterns: &ignore_list.white,
-                                ..cfg
-                            },
-                        )
-                    })
-                    .collect::<Vec<_>>();
+                    let ignore_list = if cfg.use_gitignores {
+                        VcsIgnore::in_dir_or_default(obj)
+                    } else {
+                        VcsIgnore::default()
+                    }
+                    .compose(cfg.vcs_blacklist_patterns, cfg.vcs_whitelist_patterns);
+
+                    fs::read_dir(obj)
+                        .unwrap()
+                        .map(Result::unwrap)
+                        .filter(|e| !cfg.dirs_only || e.metadata().unwrap().is_dir())
+                        .collect::<Vec<_>>()
+                        .par_iter()
+                        .filter_map(|e| {
+                            Self::from(
+                                &e.path(),
+                                SearchOpts {
+                                    max_depth,
+                                    vcs_blacklist_patterns: &ignore_list.black,
+                                    vcs_whitelist_patterns: &ignore_list.white,
+                                    ..cfg
+                                },
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                let size = if cfg.compute_size && cfg.aggregate_dir_size {
+                    Some(contents.iter().filter_map(|c| c.size).sum())
+                } else {
+                    None
+                };
+
+                let git_status = Self::worst_git_status(Self::own_git_status(cfg, obj), &contents);
 
                 Some(Self {
                     path: obj.to_owned(),
                     ftype: FType::Dir,
                     read_only: obj.metadata().unwrap().permissions().readonly(),
+                    mod_time: obj.metadata().unwrap().modified().ok(),
                     contents,
+                    size,
+                    git_status,
+                    truncated,
                     ..Self::default()
                 })
             } else {
@@ -197,6 +554,8 @@ impl Dir {
                     path: obj.to_owned(),
                     ftype: FType::Link(obj.read_link().unwrap()),
                     read_only: obj.metadata().unwrap().permissions().readonly(),
+                    mod_time: obj.metadata().unwrap().modified().ok(),
+                    git_status: Self::own_git_status(cfg, obj),
                     ..Self::default()
                 })
             }
@@ -211,10 +570,19 @@ impl Dir {
             }
 
             if should_stay {
+                let size = if cfg.compute_size && !obj.is_dir() {
+                    Some(obj.metadata().unwrap().len())
+                } else {
+                    None
+                };
+
                 Some(Self {
                     path: obj.to_owned(),
                     ftype: FType::is_exec(obj),
                     read_only: obj.metadata().unwrap().permissions().readonly(),
+                    size,
+                    mod_time: obj.metadata().unwrap().modified().ok(),
+                    git_status: Self::own_git_status(cfg, obj),
                     ..Self::default()
                 })
             } else {
@@ -223,6 +591,25 @@ impl Dir {
         }
     }
 
+    /// Look up `obj`'s git status, canonicalizing first since `git_status_map`
+    /// is keyed by absolute paths but `obj` may be relative (e.g. the default
+    /// `.` root and its `./`-prefixed children). Skipped entirely when `--git`
+    /// is off, so the extra `canonicalize()` syscall is never paid for free.
+    fn own_git_status(cfg: SearchOpts, obj: &PathBuf) -> Option<GitStatus> {
+        let map = cfg.git_status?;
+        let canonical = obj.canonicalize().ok()?;
+        map.get(&canonical).copied()
+    }
+
+    /// Summarize a directory's own status together with its children's, keeping
+    /// the "worst" one so a modification anywhere in the subtree is visible.
+    fn worst_git_status(own: Option<GitStatus>, contents: &[Self]) -> Option<GitStatus> {
+        contents
+            .iter()
+            .filter_map(|c| c.git_status)
+            .fold(own, |acc, s| Some(acc.map_or(s, |a| a.worst(s))))
+    }
+
     fn with_nest_level(self, nest: Vec<bool>) -> Self {
         Self { nest, ..self }
     }
@@ -260,6 +647,20 @@ impl Dir {
         quoted
     }
 
+    fn size_label(&self) -> Option<String> {
+        if !self.format.show_size {
+            return None;
+        }
+
+        let size = self.size?;
+
+        Some(if self.format.human_size || self.format.si_size {
+            format!("[{:>6}]", human_readable_size(size, self.format.si_size))
+        } else {
+            format!("[{:>10}]", size)
+        })
+    }
+
     fn format_name(&self) -> ColoredString {
         let mut owned = self.stringify_name().normal();
 
@@ -274,11 +675,52 @@ impl Dir {
                 FType::Link(loc) => format!("{} -> {:?}", owned.cyan().bold(), loc).normal(),
                 FType::File => owned,
             };
+
+            owned = match self.git_status {
+                Some(GitStatus::Added) | Some(GitStatus::Untracked) => owned.green(),
+                Some(GitStatus::Modified) => owned.yellow(),
+                Some(GitStatus::Deleted) | Some(GitStatus::Renamed) => owned.red(),
+                Some(GitStatus::Ignored) | None => owned,
+            };
         }
 
         owned
     }
 
+    fn git_label(&self) -> Option<String> {
+        self.git_status.map(|s| format!("[{}]", s.label()))
+    }
+
+    fn truncated_label(&self) -> Option<&'static str> {
+        if self.truncated {
+            Some(" [exceeds filelimit, not opened]")
+        } else {
+            None
+        }
+    }
+
+    fn mod_time_label(&self) -> Option<String> {
+        if !self.format.show_mod_time {
+            return None;
+        }
+
+        let time = self.mod_time?;
+        let local: DateTime<Local> = time.into();
+
+        Some(if let Some(fmt) = &self.format.time_format {
+            local.format(fmt).to_string()
+        } else {
+            const SIX_MONTHS_SECS: u64 = 60 * 60 * 24 * 30 * 6;
+            let age = SystemTime::now().duration_since(time).unwrap_or_default();
+
+            if age.as_secs() < SIX_MONTHS_SECS {
+                local.format("%b %d %H:%M").to_string()
+            } else {
+                local.format("%b %d  %Y").to_string()
+            }
+        })
+    }
+
     pub fn sort_children(&mut self) {
         self.contents.sort_unstable_by_key(|v| v.path.clone());
         self.contents.iter_mut().for_each(|c| c.sort_children());
@@ -297,6 +739,30 @@ impl Dir {
         }
     }
 
+    /// Count directories/files/executables/symlinks below this node, the way
+    /// classic `tree` reports totals without counting the root itself.
+    pub fn summarize(&self) -> Summary {
+        self.contents
+            .iter()
+            .map(Self::count_subtree)
+            .fold(Summary::default(), Summary::fold)
+    }
+
+    fn count_subtree(&self) -> Summary {
+        // A directory's own `size` is already the recursive sum of its
+        // descendants, so it must be excluded here or every file's bytes
+        // would be added once per enclosing directory.
+        let own_size = match self.ftype {
+            FType::Dir => None,
+            _ => self.size,
+        };
+
+        self.contents
+            .iter()
+            .map(Self::count_subtree)
+            .fold(Summary::leaf(&self.ftype, own_size), Summary::fold)
+    }
+
     pub fn prune(&mut self) {
         let mut contents = replace(&mut self.contents, Vec::new())
             .into_iter()
@@ -308,7 +774,14 @@ impl Dir {
         self.contents = contents;
     }
 
-    pub fn to_html(&self) -> String {
+    pub fn to_html(&self, summary: Option<Summary>) -> String {
+        let footer = summary.map_or_else(String::new, |s| {
+            format!(
+                "<div class=\"report\">{}</div>",
+                s.render(self.format.human_size, self.format.si_size)
+            )
+        });
+
         format!(
             include_str!("html/template.html"),
             include_str!("html/styles.css"),
@@ -317,7 +790,7 @@ impl Dir {
             } else {
                 ""
             },
-            self.render_self_html()
+            format!("{}{}", self.render_self_html(), footer)
         )
     }
 
@@ -336,15 +809,42 @@ impl Dir {
             class = format!("{} ro", class);
         }
 
+        if let Some(status) = self.git_status {
+            class = format!("{} git-{:?}", class, status).to_lowercase();
+        }
+
+        let size_span = self.size_label().map_or_else(String::new, |label| {
+            format!("<span class=\"size\">{}</span>", label)
+        });
+
+        let git_span = self.git_label().map_or_else(String::new, |label| {
+            format!("<span class=\"git\">{}</span>", label)
+        });
+
+        let mtime_span = self.mod_time_label().map_or_else(String::new, |label| {
+            format!("<span class=\"mtime\">{}</span>", label)
+        });
+
+        let truncated_span = self.truncated_label().map_or_else(String::new, |label| {
+            format!("<span class=\"truncated\">{}</span>", label)
+        });
+
         let mut out = if self.format.html_links {
             format!(
-                "<a class=\"{}\" href=\"{}\">{}</a>",
+                "{}{}{}<a class=\"{}\" href=\"{}\">{}</a>{}",
+                git_span,
+                size_span,
+                mtime_span,
                 class,
                 self.path.to_string_lossy(),
-                name
+                name,
+                truncated_span
             )
         } else {
-            format!("<span class=\"{}\">{}</span>", class, name)
+            format!(
+                "{}{}{}<span class=\"{}\">{}</span>{}",
+                git_span, size_span, mtime_span, class, name, truncated_span
+            )
         };
 
         if !self.contents.is_empty() {
@@ -354,7 +854,7 @@ impl Dir {
                     "<li>{}</li>",
                     element
                         .to_owned()
-                        .with_format(self.format)
+                        .with_format(self.format.clone())
                         .render_self_html()
                 ));
             }
@@ -367,14 +867,33 @@ impl Dir {
 
 impl fmt::Display for Dir {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", self.format_name())?;
+        if let Some(label) = self.git_label() {
+            write!(f, "{} ", label)?;
+        }
+
+        if let Some(label) = self.size_label() {
+            write!(f, "{}  ", label)?;
+        }
+
+        if let Some(label) = self.mod_time_label() {
+            write!(f, "{}  ", label)?;
+        }
+
+        write!(f, "{}", self.format_name())?;
+
+        if let Some(label) = self.truncated_label() {
+            write!(f, "{}", label)?;
+        }
+
+        writeln!(f)?;
 
         for (idx, member) in self.contents.iter().enumerate() {
-            let mut hanger = PARENT_NTH;
+            let charset = self.format.charset;
+            let mut hanger = charset.parent_nth;
             let mut new_depth = self.nest.clone();
 
             if idx + 1 == self.contents.len() {
-                hanger = PARENT_LAST;
+                hanger = charset.parent_last;
                 new_depth.push(false);
             } else {
                 new_depth.push(true);
@@ -383,16 +902,20 @@ impl fmt::Display for Dir {
             let adjusted_member = member
                 .to_owned()
                 .with_nest_level(new_depth)
-                .with_format(self.format);
+                .with_format(self.format.clone());
 
             if self.format.indent {
                 let space_before = self
                     .nest
                     .iter()
-                    .map(|b| format!("{:4}", if *b { SUPER_DIR } else { ' ' }))
+                    .map(|b| format!("{:4}", if *b { charset.super_dir } else { " " }))
                     .collect::<String>();
 
-                write!(f, "{}{}{}{}", space_before, hanger, INDENT, adjusted_member)?;
+                write!(
+                    f,
+                    "{}{}{}{}",
+                    space_before, hanger, charset.indent, adjusted_member
+                )?;
             } else {
                 write!(f, "{}", adjusted_member)?;
             }